@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+/// User- and platform-specific settings for locating and launching Emacs
+/// daemons.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Base directory Emacs places Unix-socket daemons under, e.g. `/tmp`.
+    pub tmp_dir: &'static str,
+    /// Socket name used when no explicit daemon name is given.
+    pub default_socket: String,
+    /// Directory Emacs places its Windows server files under, e.g.
+    /// `%APPDATA%/.emacs.d/server`.
+    pub server_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            tmp_dir: "/tmp",
+            default_socket: String::from("server"),
+            server_dir: default_server_dir(),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn default_server_dir() -> PathBuf {
+    std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".emacs.d")
+        .join("server")
+}
+
+#[cfg(not(windows))]
+fn default_server_dir() -> PathBuf {
+    PathBuf::new()
+}
@@ -0,0 +1,309 @@
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, Process, ProcessExt, System, SystemExt};
+
+use crate::config::Config;
+
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(unix)]
+pub use unix::UnixDaemonProcess as DaemonProcess;
+#[cfg(windows)]
+pub use windows::WindowsDaemonProcess as DaemonProcess;
+
+/// The signal used to ask (or force) a daemon process to exit.
+///
+/// Wrapped so callers don't need to depend on `sysinfo::Signal` directly,
+/// since POSIX signals aren't meaningful on platforms like Windows.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum KillSignal {
+    /// Ask the process to terminate, giving it a chance to clean up.
+    Term,
+    /// Force the process to exit immediately.
+    Kill,
+}
+
+/// How a `kill_graceful` shutdown went.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// The daemon exited on its own after `Signal::Term`. On Windows, where
+    /// `Term` already terminates the process outright, this is what any
+    /// confirmed shutdown reports.
+    Graceful,
+    /// The daemon didn't exit in time and had to be killed with `Signal::Kill`.
+    Forced,
+    /// The daemon could not be confirmed dead even after escalating to `Signal::Kill`.
+    Failed,
+}
+
+/// How often to re-check process/socket state while waiting on a daemon
+/// to exit (`kill_graceful`) or become ready (`launch_daemon_and_wait`).
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Behaviour that differs between platforms: how an Emacs daemon process
+/// is recognised from the system process table, where its socket (or
+/// server file, on Windows) lives, and how it is terminated.
+pub(crate) trait PlatformDaemon: Sized {
+    fn from_sys_process(p: &Process) -> Option<Self>;
+
+    fn pid(&self) -> Pid;
+
+    fn socket_name(&self) -> &str;
+
+    fn kill_with(&self, signal: KillSignal) -> Result<Pid, std::io::Error>;
+
+    fn socket_file(&self, config: &Config) -> Result<PathBuf, std::io::Error>;
+
+    /// Where a daemon called `socket_name` would put its socket (or
+    /// server file, on Windows) once it's ready, computed without an
+    /// existing `DaemonProcess` — used to wait for a freshly-launched
+    /// daemon to come up.
+    fn expected_socket_path(socket_name: &str, config: &Config) -> Result<PathBuf, std::io::Error>;
+
+    /// Ask the daemon to shut down, escalating to a forced kill if it
+    /// doesn't exit within `timeout`, and confirm the socket file is
+    /// actually released (removing it if Emacs left it behind).
+    fn kill_graceful(&self, config: &Config, timeout: Duration) -> Result<ShutdownOutcome, std::io::Error> {
+        // Best-effort: if the process has already gone, the poll loop
+        // below will notice and report success regardless.
+        let _ = self.kill_with(KillSignal::Term);
+        if self.await_exit(config, timeout) {
+            return Ok(ShutdownOutcome::Graceful);
+        }
+
+        // The process can exit in the gap between `await_exit`'s last
+        // poll and this call, in which case `kill_with` reports "no such
+        // process" - tolerate that instead of turning an actual
+        // successful shutdown into a hard error.
+        if let Err(e) = self.kill_with(KillSignal::Kill) {
+            if System::new_all().process(self.pid()).is_some() {
+                return Err(e);
+            }
+        }
+        if self.await_exit(config, timeout) {
+            return Ok(ShutdownOutcome::Forced);
+        }
+
+        Ok(ShutdownOutcome::Failed)
+    }
+
+    /// Poll until the process is gone and its socket file (if any) has
+    /// been cleared, or `timeout` elapses.
+    fn await_exit(&self, config: &Config, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if System::new_all().process(self.pid()).is_none() {
+                // Emacs usually unlinks its own socket on the way out, but
+                // clean up a stale one if it didn't get the chance to.
+                if let Ok(socket_file) = self.socket_file(config) {
+                    let _ = std::fs::remove_file(socket_file);
+                }
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn show(&self, config: &Config) -> String {
+        format!(
+            "{:<14} [{}, {}]",
+            self.socket_name(),
+            format!("Pid: {:>8}", format!("{}", self.pid())),
+            format!("Socket: {:<30} ",
+                self.socket_file(config)
+                .expect("problem with socket file...")
+                .to_str()
+                .expect("path has invalid chars")
+            ),
+        )
+    }
+}
+
+
+pub fn get_daemons() -> Vec<DaemonProcess> {
+    System::new_all().processes().iter()
+        .filter(|(_, p)| p.name().to_lowercase().starts_with("emacs"))
+        .filter(|(_, p)| match p.cmd().get(1) {
+            Some(args) => args.contains("daemon"),
+            None => false,
+        })
+        .map(|(_, p)| DaemonProcess::from_sys_process(p))
+        .flatten()
+        .collect()
+}
+
+
+pub fn list_daemons(config: &Config) -> Result<(), std::io::Error> {
+    println!("Current Emacs daemon instances:");
+    get_daemons().iter().for_each(|daemon| {
+        println!("{}", daemon.show(&config));
+    });
+    Ok(())
+}
+
+
+pub fn active_daemons_names() -> Vec<String> {
+    get_daemons().iter()
+        .map(|d| d.socket_name().to_owned())
+        .collect()
+}
+
+
+
+
+/// should return a type which captures either: Child process for a newly-spawned Emacs daemon, or a Process capturing the
+pub fn launch_daemon(name: Option<&str>, config: &Config) -> std::io::Result<Child> {
+    let daemon_name = match name {
+        Some(name) => name,
+        None => &config.default_socket,
+    };
+    Command::new("emacs")
+        .arg(format!("--daemon={}", daemon_name))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+}
+// TODO: (above) look into std::process::Commmand::{current_dir, envs}
+
+
+/// Launch a daemon and block until it's actually serving requests.
+///
+/// Polls for the daemon's expected socket file to appear while also
+/// watching the spawned process. `emacs --daemon=NAME` double-forks: the
+/// process we spawn exits (successfully) almost as soon as it hands off
+/// to the detached, reparented daemon, so a zero exit here is expected
+/// and ignored. A *non-zero* exit means Emacs bailed out before it could
+/// fork off the real daemon, e.g. because of a broken `init.el` - in
+/// that case its stderr is drained and returned as the error so the
+/// caller sees Emacs's own backtrace instead of a bare "daemon didn't
+/// start".
+pub fn launch_daemon_and_wait(name: Option<&str>, config: &Config, timeout: Duration) -> std::io::Result<Child> {
+    let daemon_name = match name {
+        Some(name) => name,
+        None => &config.default_socket,
+    }.to_owned();
+
+    let mut child = launch_daemon(Some(&daemon_name), config)?;
+    let socket_path = DaemonProcess::expected_socket_path(&daemon_name, config)?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if socket_path.exists() {
+            return Ok(child);
+        }
+        if let Some(status) = child.try_wait()? {
+            if !status.success() {
+                let stderr = child.stderr.take()
+                    .map(|pipe| drain_stderr(pipe, POLL_INTERVAL))
+                    .unwrap_or_default();
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Emacs daemon '{}' exited early with status {}:\n{}", daemon_name, status, stderr)
+                ));
+            }
+        }
+        if Instant::now() >= deadline {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("Timed out waiting for Emacs daemon '{}' to become ready.", daemon_name)
+            ));
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Read whatever `pipe` has buffered within `timeout`, without blocking
+/// indefinitely: a double-forked grandchild can keep holding the write
+/// end of this pipe open long after the process we spawned has exited,
+/// so a plain `read_to_string` could hang forever waiting for EOF.
+fn drain_stderr(mut pipe: std::process::ChildStderr, timeout: Duration) -> String {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut chunk = [0u8; 4096];
+        while let Ok(n) = pipe.read(&mut chunk) {
+            if n == 0 || tx.send(chunk[..n].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    let deadline = Instant::now() + timeout;
+    let mut buf = Vec::new();
+    while let Ok(chunk) = rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        buf.extend(chunk);
+    }
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+
+pub fn kill_daemon(name: &str, config: &Config) -> Result<(), std::io::Error> {
+    match get_daemons().iter().find(|&p| p.socket_name() == name) {
+        Some(daemon) => match daemon.kill_graceful(config, Duration::from_secs(5))? {
+            ShutdownOutcome::Graceful => {
+                println!("Daemon '{}' shut down gracefully.", name);
+                Ok(())
+            },
+            ShutdownOutcome::Forced => {
+                println!("Daemon '{}' did not exit in time and was force-killed.", name);
+                Ok(())
+            },
+            ShutdownOutcome::Failed => Err(
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Failed to confirm that Emacs daemon '{}' shut down.", name)
+                )
+            ),
+        },
+        None => Err(
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("No Emacs daemon found with socket name {}", name)
+            )
+        ),
+    }
+}
+
+
+/// Cycle a daemon: gracefully kill it (escalating to a forced kill if it
+/// doesn't exit in time) and wait for its socket to be released, then
+/// relaunch it under the same socket name. Composing `kill_graceful` and
+/// `launch_daemon_and_wait` this way is what keeps this safe; a naive
+/// `kill_daemon` followed by a separate `launch_daemon` call races the
+/// relaunch against the old socket still being cleaned up.
+pub fn restart_daemon(name: &str, config: &Config) -> Result<Pid, std::io::Error> {
+    let daemon = get_daemons().into_iter().find(|p| p.socket_name() == name).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("No Emacs daemon found with socket name {}", name)
+        )
+    })?;
+
+    match daemon.kill_graceful(config, Duration::from_secs(5))? {
+        ShutdownOutcome::Failed => return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Could not confirm that Emacs daemon '{}' shut down; refusing to restart it.", name)
+        )),
+        ShutdownOutcome::Graceful | ShutdownOutcome::Forced => {},
+    }
+
+    launch_daemon_and_wait(Some(name), config, Duration::from_secs(10))?;
+
+    // `launch_daemon_and_wait` only hands back the short-lived process
+    // that performed the double fork, not the daemon itself - so look
+    // the real, reparented daemon back up in the process table the same
+    // way `get_daemons`/`kill_daemon` already do, rather than reporting
+    // the launcher's (by-then-exited) pid.
+    get_daemons().into_iter().find(|p| p.socket_name() == name).map(|p| p.pid()).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Emacs daemon '{}' was relaunched but could not be found in the process table.", name)
+        )
+    })
+}
@@ -0,0 +1,113 @@
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, Process, ProcessExt, Signal, System, SystemExt, Uid};
+
+use crate::config::Config;
+
+use super::{KillSignal, PlatformDaemon};
+
+
+#[derive(Clone, Debug)]
+pub struct UnixDaemonProcess {
+    pub pid: Pid,
+    pub user_id: Option<Uid>,
+    pub socket_name: String,
+}
+
+impl PlatformDaemon for UnixDaemonProcess {
+    fn from_sys_process(p: &Process) -> Option<Self> {
+        // The socket name needs to be derived from the command arguments
+        // passed to emacs. These will be of the form:
+        // --bg-daemon=\xxx,y\012/name//or/socket/path
+        // The result of `p.cmd()` is therefore parsed to extract the
+        // "/name//or/socket/path" portion into a `Path`, to extract the
+        // socket filename
+        let socket_name = Path::new(p.cmd().get(1)?
+            .split_once('=')?
+            .1
+            .split('\n')
+            .last()?
+        ).file_name()?.to_str();
+
+        Some(Self {
+            pid: p.pid(),
+            user_id: p.user_id().cloned(),
+            socket_name: socket_name?.to_owned(),
+        })
+    }
+
+    fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    fn socket_name(&self) -> &str {
+        &self.socket_name
+    }
+
+    fn kill_with(&self, signal: KillSignal) -> Result<Pid, std::io::Error> {
+        let system = System::new_all();
+        let pid = self.pid;
+        let signal = match signal {
+            // Consistent with `kill PID` on MacOS/Linux and allows the
+            // Emacs daemon process to clear up its socket file.
+            KillSignal::Term => Signal::Term,
+            KillSignal::Kill => Signal::Kill,
+        };
+        match system.process(pid) {
+            Some(process) => match process.kill_with(signal) {
+                Some(true) => Ok(pid),
+                Some(false) => Err(
+                    std::io::Error::new(std::io::ErrorKind::Other,
+                    format!("Error trying to send kill signal to Emacs daemon '{}' with Pid {}.", self.socket_name, pid)
+                    )
+                ),
+                None => Err(
+                    std::io::Error::new(std::io::ErrorKind::Other, "Requested signal does not exist on this system.")
+                ),
+            },
+            None => Err(
+                std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Error trying to send kill signal to Emacs daemon. No process found with with Pid {}.", pid)
+                )
+            )
+        }
+    }
+
+    fn socket_file(&self, config: &Config) -> Result<PathBuf, std::io::Error> {
+        match &self.user_id {
+            Some(uid) => {
+                let socket_path = PathBuf::from(config.tmp_dir)
+                    .join(format!("emacs{}", uid.deref() ))
+                    .join(self.socket_name.clone());
+                match socket_path.exists() {
+                    true => Ok(socket_path),
+                    false => Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("Daemon socket at path {:?} does not exist.", socket_path)
+                    )),
+                }
+            },
+            None => Err(std::io::Error::new(std::io::ErrorKind::Other,
+                format!("Unexpected! No user ID present for Emacs daemon process:\n{:?}", self)
+            )),
+        }
+    }
+
+    fn expected_socket_path(socket_name: &str, config: &Config) -> Result<PathBuf, std::io::Error> {
+        let uid = current_user_id().ok_or_else(|| std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Could not determine the current user ID."
+        ))?;
+        Ok(PathBuf::from(config.tmp_dir)
+            .join(format!("emacs{}", uid.deref()))
+            .join(socket_name))
+    }
+}
+
+/// The user ID of the running `eud` process itself, used to predict where
+/// a daemon we're about to launch will put its socket before it exists.
+fn current_user_id() -> Option<Uid> {
+    let pid = sysinfo::get_current_pid().ok()?;
+    System::new_all().process(pid)?.user_id().cloned()
+}
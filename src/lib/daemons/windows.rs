@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+use sysinfo::{Pid, Process, ProcessExt};
+use windows_sys::Win32::Foundation::CloseHandle;
+use windows_sys::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
+
+use crate::config::Config;
+
+use super::{KillSignal, PlatformDaemon};
+
+
+#[derive(Clone, Debug)]
+pub struct WindowsDaemonProcess {
+    pub pid: Pid,
+    pub socket_name: String,
+}
+
+impl PlatformDaemon for WindowsDaemonProcess {
+    fn from_sys_process(p: &Process) -> Option<Self> {
+        // Emacs identifies the daemon the same way on Windows as on Unix:
+        // the server name is the last path segment of the `--bg-daemon=`
+        // argument, it just happens to name a server *file* rather than a
+        // socket once we get to `socket_file` below.
+        let socket_name = Path::new(p.cmd().get(1)?
+            .split_once('=')?
+            .1
+            .split('\n')
+            .last()?
+        ).file_name()?.to_str();
+
+        Some(Self {
+            pid: p.pid(),
+            socket_name: socket_name?.to_owned(),
+        })
+    }
+
+    fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    fn socket_name(&self) -> &str {
+        &self.socket_name
+    }
+
+    fn kill_with(&self, _signal: KillSignal) -> Result<Pid, std::io::Error> {
+        // Windows has no POSIX signal delivery, so there is no graceful
+        // vs. forced distinction to make here: both `Term` and `Kill`
+        // terminate the process immediately via the Win32 API.
+        let pid = self.pid;
+        unsafe {
+            let handle = OpenProcess(PROCESS_TERMINATE, 0, pid.as_u32());
+            if handle == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            let terminated = TerminateProcess(handle, 1);
+            CloseHandle(handle);
+            if terminated == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(pid)
+    }
+
+    fn socket_file(&self, config: &Config) -> Result<PathBuf, std::io::Error> {
+        // Emacs on Windows writes a server *file* (not a Unix socket)
+        // under `%APPDATA%/.emacs.d/server/<name>` holding the
+        // host/port/auth info used to connect to the daemon.
+        let server_path = config.server_dir.clone()
+            .join(self.socket_name.clone());
+        match server_path.exists() {
+            true => Ok(server_path),
+            false => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Daemon server file at path {:?} does not exist.", server_path)
+            )),
+        }
+    }
+
+    fn expected_socket_path(socket_name: &str, config: &Config) -> Result<PathBuf, std::io::Error> {
+        // Unlike Unix, there's no per-user ID to fold in: the server
+        // directory already lives under the current user's `%APPDATA%`.
+        Ok(config.server_dir.clone().join(socket_name))
+    }
+}
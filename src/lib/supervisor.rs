@@ -0,0 +1,106 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use notify_rust::Notification;
+
+use crate::config::Config;
+use crate::daemons::{active_daemons_names, get_daemons, launch_daemon_and_wait, PlatformDaemon};
+
+/// How long to wait for a crashed daemon to come back up before giving
+/// up on the restart and notifying about the failure.
+const RESTART_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How finely to slice a wait on `poll_interval` so Ctrl-C is noticed
+/// promptly instead of only once the full interval has elapsed -
+/// `std::thread::sleep` retries through `EINTR`, so a single sleep call
+/// for the whole interval would not be woken by the SIGINT handler.
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Watch a set of daemons and relaunch any that disappear from the
+/// process table, firing a desktop notification whenever one dies and
+/// again once the restart attempt has concluded. Runs until interrupted
+/// with Ctrl-C (SIGINT), leaving the daemons it manages running.
+///
+/// `names` defaults to the daemons currently running (via
+/// `active_daemons_names`) when `None`. `poll_interval` is parsed with
+/// `humantime`, e.g. `"30s"` or `"2m"`.
+pub fn supervise(config: &Config, names: Option<Vec<String>>, poll_interval: &str) -> std::io::Result<()> {
+    let poll_interval = humantime::parse_duration(poll_interval).map_err(|e| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Invalid poll interval '{}': {}", poll_interval, e)
+        )
+    })?;
+
+    let expected: HashSet<String> = match names {
+        Some(names) => names.into_iter().collect(),
+        None => active_daemons_names().into_iter().collect(),
+    };
+
+    if expected.is_empty() {
+        println!("No daemons to supervise.");
+        return Ok(());
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = Arc::clone(&running);
+        ctrlc::set_handler(move || running.store(false, Ordering::SeqCst))
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+    }
+
+    println!(
+        "Supervising {} daemon(s): {}",
+        expected.len(),
+        expected.iter().cloned().collect::<Vec<_>>().join(", ")
+    );
+
+    while running.load(Ordering::SeqCst) {
+        let alive: HashSet<String> = get_daemons().iter().map(|d| d.socket_name().to_owned()).collect();
+
+        for name in &expected {
+            if alive.contains(name) {
+                continue;
+            }
+
+            notify(&format!("Emacs daemon '{}' died", name), "Attempting to restart it...");
+            match launch_daemon_and_wait(Some(name), config, RESTART_TIMEOUT) {
+                Ok(_) => notify(
+                    &format!("Emacs daemon '{}' restarted", name),
+                    "The daemon is back up and serving."
+                ),
+                Err(e) => notify(
+                    &format!("Failed to restart Emacs daemon '{}'", name),
+                    &e.to_string()
+                ),
+            }
+        }
+
+        sleep_while_running(&running, poll_interval);
+    }
+
+    println!("Supervisor stopped.");
+    Ok(())
+}
+
+/// Sleep for `duration`, but in slices short enough that a Ctrl-C
+/// setting `running` to `false` partway through is noticed quickly
+/// instead of only after the whole `duration` has elapsed.
+fn sleep_while_running(running: &AtomicBool, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while running.load(Ordering::SeqCst) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return;
+        }
+        std::thread::sleep(remaining.min(SHUTDOWN_CHECK_INTERVAL));
+    }
+}
+
+fn notify(summary: &str, body: &str) {
+    if let Err(e) = Notification::new().summary(summary).body(body).show() {
+        eprintln!("Failed to send desktop notification: {}", e);
+    }
+}